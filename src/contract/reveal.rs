@@ -11,16 +11,20 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use amplify::Wrapper;
 use lnpbp::client_side_validation::{
     CommitConceal, CommitEncode, ToMerkleSource,
 };
+use secp256k1zkp::pedersen;
 
 use super::OwnedRightsInner;
 use crate::schema::NodeType;
-use crate::{Assignments, OwnedRights, OwnedState, StateTypes};
+use crate::{
+    Anchor, Assignments, DeclarativeStrategy, HashStrategy, Node, OwnedRights,
+    OwnedState, PedersenStrategy, StateTypes, Transition, TransitionBundle,
+};
 
 /// Merge Error generated in merging operation
 #[derive(
@@ -53,6 +57,14 @@ pub enum Error {
     /// Node of type {0} has different commitment ids and can't be
     /// reveal-merged
     NodeMismatch(NodeType),
+
+    /// Revealed state does not open the confidential commitment it is merged
+    /// against
+    InvalidOpening,
+
+    /// Non-fungible state type carries a duplicated revealed datum within a
+    /// single owned-right slot
+    NonUniqueState,
 }
 
 /// A trait to merge two structures modifying the revealed status
@@ -72,12 +84,133 @@ pub enum Error {
 /// merge(Confidential, Anything) = Anything
 pub trait IntoRevealed: Sized {
     fn into_revealed(self, other: Self) -> Result<Self, Error>;
+
+    /// Folds an iterator of same-id structures into their
+    /// maximally-revealed join.
+    ///
+    /// The reveal order `Confidential ⊑ {ConfidentialSeal, ConfidentialAmount}
+    /// ⊑ Revealed` is a bounded join-semilattice, so the fold is independent
+    /// of the iteration order. Returns `None` for an empty iterator and
+    /// aborts with the first error encountered (a `*Mismatch`, or an
+    /// [`Error::InvalidOpening`] or [`Error::NonUniqueState`]).
+    fn merge_reveal_all(
+        iter: impl IntoIterator<Item = Self>,
+    ) -> Result<Option<Self>, Error> {
+        iter.into_iter().try_fold(None, |acc, item| match acc {
+            None => Ok(Some(item)),
+            Some(acc) => acc.into_revealed(item).map(Some),
+        })
+    }
+
+    /// Non-fatal counterpart of [`IntoRevealed::merge_reveal_all`].
+    ///
+    /// Instead of aborting on the first error it folds the whole collection,
+    /// keeping the running maximally-revealed value and dropping every item
+    /// that fails to join, and returns that value together with a report of
+    /// all errors encountered. Used to reconcile
+    /// many overlapping consignments arriving out of order into a stash.
+    fn merge_reveal_all_lenient(
+        iter: impl IntoIterator<Item = Self>,
+    ) -> MergeReport<Self>
+    where
+        Self: Clone,
+    {
+        let mut merged: Option<Self> = None;
+        let mut conflicts = Vec::new();
+        for item in iter {
+            merged = Some(match merged {
+                None => item,
+                Some(acc) => match acc.clone().into_revealed(item) {
+                    Ok(joined) => joined,
+                    Err(err) => {
+                        conflicts.push(err);
+                        acc
+                    }
+                },
+            });
+        }
+        MergeReport { merged, conflicts }
+    }
+}
+
+/// Outcome of a non-fatal batch reveal-merge
+/// ([`IntoRevealed::merge_reveal_all_lenient`]).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MergeReport<T> {
+    /// Maximally-revealed value obtained by joining every compatible item,
+    /// or `None` if the input collection was empty.
+    pub merged: Option<T>,
+
+    /// Every error encountered while folding, one per dropped item:
+    /// commitment-id conflicts as well as failed openings and duplicated
+    /// non-fungible data; empty when all items joined cleanly.
+    pub conflicts: Vec<Error>,
+}
+
+/// Verifies that a revealed value actually opens the confidential commitment
+/// it is merged against, for a given owned-state strategy.
+///
+/// Strategies with no homomorphic commitment to open — declarative rights,
+/// custom data — have nothing to verify and accept unconditionally.
+/// [`PedersenStrategy`] recomputes `v·H + r·G` from the revealed value `v`
+/// and blinding factor `r` and compares it to the stored commitment.
+trait VerifyOpening: StateTypes {
+    /// Accepts unconditionally by default, since most strategies carry no
+    /// homomorphic commitment to open. Only [`PedersenStrategy`] overrides
+    /// this with an actual opening check.
+    fn verify_opening(
+        _revealed: &Self::Revealed,
+        _confidential: &Self::Confidential,
+    ) -> bool {
+        true
+    }
 }
 
+impl VerifyOpening for DeclarativeStrategy {}
+
+impl VerifyOpening for HashStrategy {}
+
+impl VerifyOpening for PedersenStrategy {
+    fn verify_opening(
+        revealed: &<Self as StateTypes>::Revealed,
+        confidential: &<Self as StateTypes>::Confidential,
+    ) -> bool {
+        let secp = secp256k1zkp::Secp256k1::with_caps(
+            secp256k1zkp::ContextFlag::Commit,
+        );
+        match secp.commit(revealed.value, revealed.blinding) {
+            Ok(opened) => opened == confidential.commitment,
+            Err(_) => false,
+        }
+    }
+}
+
+/// Marks strategies whose revealed value is worth deduplicating within an
+/// owned-right slot.
+///
+/// Non-fungible state (no homomorphic sum to balance, see
+/// [`Assignments::verify_balance`]) must otherwise stay unique per slot once
+/// revealed, so two distinct seals never end up claiming the same datum —
+/// except declarative rights, whose "revealed" value is the same unit for
+/// every assignment, so there is nothing there to distinguish one from
+/// another and the uniqueness check would reject the ordinary case of one
+/// declarative right assigned to several seals.
+trait HasDistinguishingDatum: StateTypes {
+    const HAS_DISTINGUISHING_DATUM: bool = true;
+}
+
+impl HasDistinguishingDatum for DeclarativeStrategy {
+    const HAS_DISTINGUISHING_DATUM: bool = false;
+}
+
+impl HasDistinguishingDatum for HashStrategy {}
+
+impl HasDistinguishingDatum for PedersenStrategy {}
+
 impl<STATE> IntoRevealed for OwnedState<STATE>
 where
     Self: Clone,
-    STATE: StateTypes,
+    STATE: VerifyOpening,
     STATE::Confidential: PartialEq + Eq,
     STATE::Confidential:
         From<<STATE::Revealed as CommitConceal>::ConcealedCommitment>,
@@ -85,57 +218,88 @@ where
     fn into_revealed(self, other: Self) -> Result<Self, Error> {
         // if self and other is different through error
         if self.commit_serialize() != other.commit_serialize() {
-            Err(Error::OwnedStateMismatch)
-        } else {
-            match (self, other) {
-                // Anything + Revealed = Revealed
-                (_, state @ OwnedState::Revealed { .. })
-                | (state @ OwnedState::Revealed { .. }, _) => Ok(state),
+            return Err(Error::OwnedStateMismatch);
+        }
 
-                // ConfidentialAmount + ConfidentialSeal = Revealed
-                (
-                    OwnedState::ConfidentialSeal {
-                        assigned_state: state,
-                        ..
-                    },
-                    OwnedState::ConfidentialAmount {
-                        seal_definition: seal,
-                        ..
-                    },
-                ) => Ok(OwnedState::Revealed {
-                    seal_definition: seal,
+        // When one side reveals the state while the other only holds its
+        // confidential commitment, verify that the revealed value genuinely
+        // opens that commitment before trusting it. This runs for every
+        // strategy so the guarantee cannot be bypassed by calling this
+        // primitive directly instead of going through `Assignments`.
+        if let (Some(revealed), Some(confidential)) = (
+            self.assigned_state().or_else(|| other.assigned_state()),
+            self.confidential_state().or_else(|| other.confidential_state()),
+        ) {
+            if !STATE::verify_opening(revealed, confidential) {
+                return Err(Error::InvalidOpening);
+            }
+        }
+
+        match (self, other) {
+            // Anything + Revealed = Revealed
+            (_, state @ OwnedState::Revealed { .. })
+            | (state @ OwnedState::Revealed { .. }, _) => Ok(state),
+
+            // ConfidentialAmount + ConfidentialSeal = Revealed
+            (
+                OwnedState::ConfidentialSeal {
                     assigned_state: state,
-                }),
+                    ..
+                },
+                OwnedState::ConfidentialAmount {
+                    seal_definition: seal,
+                    ..
+                },
+            ) => Ok(OwnedState::Revealed {
+                seal_definition: seal,
+                assigned_state: state,
+            }),
 
-                // ConfidentialSeal + ConfidentialAmount = Revealed
-                (
-                    OwnedState::ConfidentialAmount {
-                        seal_definition: seal,
-                        ..
-                    },
-                    OwnedState::ConfidentialSeal {
-                        assigned_state: state,
-                        ..
-                    },
-                ) => Ok(OwnedState::Revealed {
+            // ConfidentialSeal + ConfidentialAmount = Revealed
+            (
+                OwnedState::ConfidentialAmount {
                     seal_definition: seal,
+                    ..
+                },
+                OwnedState::ConfidentialSeal {
                     assigned_state: state,
-                }),
+                    ..
+                },
+            ) => Ok(OwnedState::Revealed {
+                seal_definition: seal,
+                assigned_state: state,
+            }),
 
-                // if self and other is of same variant return self
-                (
-                    state @ OwnedState::ConfidentialAmount { .. },
-                    OwnedState::ConfidentialAmount { .. },
-                ) => Ok(state),
-                (
-                    state @ OwnedState::ConfidentialSeal { .. },
-                    OwnedState::ConfidentialSeal { .. },
-                ) => Ok(state),
+            // if self and other is of same variant return self
+            (
+                state @ OwnedState::ConfidentialAmount { .. },
+                OwnedState::ConfidentialAmount { .. },
+            ) => Ok(state),
+            (
+                state @ OwnedState::ConfidentialSeal { .. },
+                OwnedState::ConfidentialSeal { .. },
+            ) => Ok(state),
+
+            // Anything + Confidential = Anything
+            (state, OwnedState::Confidential { .. })
+            | (OwnedState::Confidential { .. }, state) => Ok(state),
+        }
+    }
+}
 
-                // Anything + Confidential = Anything
-                (state, OwnedState::Confidential { .. })
-                | (OwnedState::Confidential { .. }, state) => Ok(state),
+impl<STATE: StateTypes> OwnedState<STATE> {
+    /// Returns the confidential commitment this state carries, if its state
+    /// is concealed (`Confidential` or `ConfidentialAmount`); `None` for
+    /// `Revealed` and `ConfidentialSeal`. Mirrors [`OwnedState::assigned_state`]
+    /// for the confidential side, used to recover both halves of an
+    /// opening-verification pair regardless of which side reveals what.
+    fn confidential_state(&self) -> Option<&STATE::Confidential> {
+        match self {
+            OwnedState::Confidential { assigned_state, .. }
+            | OwnedState::ConfidentialAmount { assigned_state, .. } => {
+                Some(assigned_state)
             }
+            _ => None,
         }
     }
 }
@@ -149,41 +313,20 @@ impl IntoRevealed for Assignments {
                 (
                     Assignments::Declarative(first_vec),
                     Assignments::Declarative(second_vec),
-                ) => {
-                    let mut result = Vec::with_capacity(first_vec.len());
-                    for (first, second) in
-                        first_vec.into_iter().zip(second_vec.into_iter())
-                    {
-                        result.push(first.into_revealed(second)?);
-                    }
-                    Ok(Assignments::Declarative(result))
-                }
+                ) => Self::merge_owned_states(first_vec, second_vec)
+                    .map(Assignments::Declarative),
 
                 (
                     Assignments::DiscreteFiniteField(first_vec),
                     Assignments::DiscreteFiniteField(second_vec),
-                ) => {
-                    let mut result = Vec::with_capacity(first_vec.len());
-                    for (first, second) in
-                        first_vec.into_iter().zip(second_vec.into_iter())
-                    {
-                        result.push(first.into_revealed(second)?);
-                    }
-                    Ok(Assignments::DiscreteFiniteField(result))
-                }
+                ) => Self::merge_owned_states(first_vec, second_vec)
+                    .map(Assignments::DiscreteFiniteField),
 
                 (
                     Assignments::CustomData(first_vec),
                     Assignments::CustomData(second_vec),
-                ) => {
-                    let mut result = Vec::with_capacity(first_vec.len());
-                    for (first, second) in
-                        first_vec.into_iter().zip(second_vec.into_iter())
-                    {
-                        result.push(first.into_revealed(second)?);
-                    }
-                    Ok(Assignments::CustomData(result))
-                }
+                ) => Self::merge_owned_states(first_vec, second_vec)
+                    .map(Assignments::CustomData),
                 // No other patterns possible, should not reach here
                 _ => {
                     unreachable!("Assignments::consensus_commitments is broken")
@@ -193,6 +336,113 @@ impl IntoRevealed for Assignments {
     }
 }
 
+impl Assignments {
+    /// Returns whether this assignment group holds fungible state, read off
+    /// `StateTypes::IS_FUNGIBLE` for the strategy backing the variant rather
+    /// than assumed from the variant name. Lets schema validation reject
+    /// mixing fungible and non-fungible assignments under one state type
+    /// without every caller having to pattern-match the variant.
+    pub fn is_fungible(&self) -> bool {
+        match self {
+            Assignments::Declarative(_) => DeclarativeStrategy::IS_FUNGIBLE,
+            Assignments::DiscreteFiniteField(_) => {
+                PedersenStrategy::IS_FUNGIBLE
+            }
+            Assignments::CustomData(_) => HashStrategy::IS_FUNGIBLE,
+        }
+    }
+
+    /// Reveal-merges a pair of same-length owned-state vectors element by
+    /// element. The per-element opening check lives on
+    /// [`OwnedState::into_revealed`] itself, so it applies uniformly here
+    /// regardless of which variant is being merged. The merge policy beyond
+    /// that is driven by `STATE::IS_FUNGIBLE`, not by which `Assignments`
+    /// variant called in: fungible state has a homomorphic sum to balance
+    /// (see [`Assignments::verify_balance`]) and needs no further per-element
+    /// check here, while non-fungible state has no such sum and instead must
+    /// stay unique per owned-right slot once revealed, so two distinct seals
+    /// never end up claiming the same datum — except strategies with nothing
+    /// to distinguish one revealed value from another (see
+    /// [`HasDistinguishingDatum`]), which are exempt from that check.
+    fn merge_owned_states<STATE>(
+        first: Vec<OwnedState<STATE>>,
+        second: Vec<OwnedState<STATE>>,
+    ) -> Result<Vec<OwnedState<STATE>>, Error>
+    where
+        STATE: VerifyOpening + HasDistinguishingDatum,
+        STATE::Confidential: PartialEq + Eq,
+        STATE::Confidential:
+            From<<STATE::Revealed as CommitConceal>::ConcealedCommitment>,
+        STATE::Revealed: Hash + Eq + Clone,
+    {
+        let mut result = Vec::with_capacity(first.len());
+        for (first, second) in first.into_iter().zip(second.into_iter()) {
+            result.push(first.into_revealed(second)?);
+        }
+        if !STATE::IS_FUNGIBLE && STATE::HAS_DISTINGUISHING_DATUM {
+            let mut seen = HashSet::new();
+            for state in &result {
+                if let Some(data) = state.assigned_state() {
+                    if !seen.insert(data.clone()) {
+                        return Err(Error::NonUniqueState);
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Collects the confidential Pedersen commitment of every fungible
+    /// assignment held by `set`, concealing revealed amounts on the fly.
+    /// Non-fungible assignment groups carry no amount and contribute nothing.
+    fn confidential_amounts(set: &Assignments) -> Vec<pedersen::Commitment> {
+        match set {
+            Assignments::DiscreteFiniteField(states) => states
+                .iter()
+                .map(|state| match state {
+                    OwnedState::Revealed { assigned_state, .. }
+                    | OwnedState::ConfidentialSeal { assigned_state, .. } => {
+                        assigned_state.commit_conceal().commitment
+                    }
+                    OwnedState::Confidential { assigned_state, .. }
+                    | OwnedState::ConfidentialAmount { assigned_state, .. } => {
+                        assigned_state.commitment
+                    }
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Verifies Pedersen balance preservation across a transition, i.e. that
+    /// the homomorphic sum of the confidential amounts consumed by `inputs`
+    /// equals the sum of the amounts created by `outputs`.
+    ///
+    /// Because Pedersen commitments are additively homomorphic
+    /// (`Σ Cᵢ = (Σ vᵢ)·H + (Σ rᵢ)·G`), the check can be performed directly on
+    /// the commitments without opening them, and therefore works even while
+    /// some of the states remain confidential. Combined with the per-state
+    /// opening verification in [`IntoRevealed`], this guarantees that a
+    /// revealed consignment neither inflates nor burns value.
+    pub fn verify_balance<'a>(
+        inputs: impl IntoIterator<Item = &'a Assignments>,
+        outputs: impl IntoIterator<Item = &'a Assignments>,
+    ) -> bool {
+        let secp = secp256k1zkp::Secp256k1::with_caps(
+            secp256k1zkp::ContextFlag::Commit,
+        );
+        let inputs = inputs
+            .into_iter()
+            .flat_map(Self::confidential_amounts)
+            .collect::<Vec<_>>();
+        let outputs = outputs
+            .into_iter()
+            .flat_map(Self::confidential_amounts)
+            .collect::<Vec<_>>();
+        secp.verify_commit_sum(outputs, inputs)
+    }
+}
+
 impl IntoRevealed for OwnedRightsInner {
     fn into_revealed(self, other: Self) -> Result<Self, Error> {
         if self.to_merkle_source().commit_serialize()
@@ -212,6 +462,75 @@ impl IntoRevealed for OwnedRightsInner {
     }
 }
 
+impl IntoRevealed for Anchor {
+    fn into_revealed(self, other: Self) -> Result<Self, Error> {
+        // Both anchors must commit to the same multi-protocol-commitment
+        // root; otherwise they witness different states and merging them would
+        // silently corrupt the stash. Unlike `OwnedState`/`Assignments`, an
+        // `Anchor`'s `commit_serialize()` is not guaranteed invariant across
+        // reveal degrees (a partial MPC root and its fully-revealed
+        // counterpart do not necessarily serialize identically), so compare
+        // the commitment id instead, which is.
+        if self.commitment_id() != other.commitment_id() {
+            return Err(Error::AnchorsMismatch);
+        }
+        // One side may hold only the MPC root while the other carries the
+        // full MPC inclusion path and the deterministic-bitcoin-commitment
+        // proof (tapret- or opret-style). Delegate to the anchor's own
+        // reveal-merge, which keeps the maximally-revealed version of each.
+        self.merge_reveal(other).map_err(|_| Error::AnchorsMismatch)
+    }
+}
+
+impl IntoRevealed for Transition {
+    fn into_revealed(self, other: Self) -> Result<Self, Error> {
+        // metadata, transition type and parent ownership relations are part
+        // of the node commitment and thus already equal once the ids match;
+        // only the owned rights may carry a different degree of revelation.
+        if self.node_id() != other.node_id() {
+            return Err(Error::NodeMismatch(NodeType::StateTransition));
+        }
+        let owned_rights = OwnedRightsInner::from(self.owned_rights().clone())
+            .into_revealed(OwnedRightsInner::from(
+                other.owned_rights().clone(),
+            ))?
+            .into_inner();
+        let mut result = self;
+        *result.owned_rights_mut() = owned_rights;
+        Ok(result)
+    }
+}
+
+impl IntoRevealed for TransitionBundle {
+    fn into_revealed(self, other: Self) -> Result<Self, Error> {
+        if self.bundle_id() != other.bundle_id() {
+            return Err(Error::NodeMismatch(NodeType::StateTransition));
+        }
+        // The bundle is a `BTreeMap` keyed by `Transition`, so two copies of
+        // the same bundle that reveal a transition to different degrees sort
+        // its key to different positions. Index the counterpart by `node_id`
+        // and look it up, rather than pairing by position, so each transition
+        // is merged against its true counterpart.
+        let mut others: BTreeMap<_, _> = other
+            .into_iter()
+            .map(|(transition, inputs)| {
+                (transition.node_id(), (transition, inputs))
+            })
+            .collect();
+        let mut result = BTreeMap::new();
+        for (transition, inputs) in self.into_iter() {
+            let (other_transition, other_inputs) = others
+                .remove(&transition.node_id())
+                .ok_or(Error::NodeMismatch(NodeType::StateTransition))?;
+            let transition = transition.into_revealed(other_transition)?;
+            let mut inputs = inputs;
+            inputs.extend(other_inputs);
+            result.insert(transition, inputs);
+        }
+        Ok(TransitionBundle::from(result))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -227,6 +546,86 @@ mod test {
     static PEDERSAN_VARIANT: [u8; 1664] =
         include!("../../test/pedersan_state.in");
 
+    // Two encodings of the same anchor, sharing a commitment id: one
+    // revealing only the MPC inclusion root, the other also carrying the
+    // full deterministic-bitcoin-commitment (tapret/opret) proof.
+    static ANCHOR_VARIANT_PARTIAL: [u8; 267] =
+        include!("../../test/anchor_partial.in");
+    static ANCHOR_VARIANT_FULL: [u8; 267] =
+        include!("../../test/anchor_full.in");
+
+    // An encoding of a state transition, plus a second, unrelated
+    // transition used to exercise the node-id mismatch path.
+    static TRANSITION_VARIANT: [u8; 267] =
+        include!("../../test/transition_reveal.in");
+    static TRANSITION_VARIANT_OTHER: [u8; 267] =
+        include!("../../test/transition_other.in");
+
+    // Two bundles built from the same pair of transitions, each revealing
+    // them to a different degree, so the two transitions sort to different
+    // positions in the bundle's `BTreeMap` depending on which side is
+    // "self" and which is "other". Regression coverage for the node-id
+    // based pairing fix: a position-based zip would merge the wrong pairs.
+    static BUNDLE_VARIANT_A: [u8; 267] = include!("../../test/bundle_a.in");
+    static BUNDLE_VARIANT_B: [u8; 267] = include!("../../test/bundle_b.in");
+
+    #[test]
+    fn test_anchor_reveal_merge() {
+        let partial =
+            Anchor::strict_decode(&ANCHOR_VARIANT_PARTIAL[..]).unwrap();
+        let full = Anchor::strict_decode(&ANCHOR_VARIANT_FULL[..]).unwrap();
+
+        // Merging must yield the fully-revealed anchor regardless of which
+        // side is `self` and which is `other`.
+        let merged = partial.clone().into_revealed(full.clone()).unwrap();
+        assert_eq!(merged, full);
+        let merged_rev = full.into_revealed(partial).unwrap();
+        assert_eq!(merged_rev, merged);
+    }
+
+    #[test]
+    fn test_transition_reveal_merge() {
+        let transition =
+            Transition::strict_decode(&TRANSITION_VARIANT[..]).unwrap();
+        let other =
+            Transition::strict_decode(&TRANSITION_VARIANT_OTHER[..]).unwrap();
+
+        // Merging a transition with itself is a no-op.
+        let merged = transition
+            .clone()
+            .into_revealed(transition.clone())
+            .unwrap();
+        assert_eq!(merged, transition);
+
+        // A transition with a different node id is not a valid merge
+        // counterpart.
+        assert_eq!(
+            transition.into_revealed(other).unwrap_err(),
+            Error::NodeMismatch(NodeType::StateTransition)
+        );
+    }
+
+    #[test]
+    fn test_transition_bundle_pairs_by_node_id() {
+        let bundle_a =
+            TransitionBundle::strict_decode(&BUNDLE_VARIANT_A[..]).unwrap();
+        let bundle_b =
+            TransitionBundle::strict_decode(&BUNDLE_VARIANT_B[..]).unwrap();
+
+        // Before the node-id pairing fix, merging these two bundles paired
+        // transitions by `BTreeMap` position rather than by `node_id`,
+        // silently joining the wrong pair whenever the two sides disagreed
+        // on which transition revealed more. The merge must succeed and
+        // produce the same, fully-revealed bundle regardless of which side
+        // is `self` and which is `other`.
+        let merged = bundle_a
+            .clone()
+            .into_revealed(bundle_b.clone())
+            .unwrap();
+        let merged_rev = bundle_b.into_revealed(bundle_a).unwrap();
+        assert_eq!(merged, merged_rev);
+    }
+
     #[test]
     fn test_into_revealed_state() {
         let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
@@ -287,6 +686,71 @@ mod test {
         assert_eq!(merged, conf);
     }
 
+    #[test]
+    fn test_invalid_opening_rejected() {
+        let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
+            .unwrap()
+            .into_discrete_state();
+
+        let rev = ass[1].clone();
+        let conf = rev.commit_conceal();
+
+        // A forged amount that does not open the confidential commitment
+        // stored alongside it is rejected, even though the pair still shares
+        // the same seal and would otherwise look like a legitimate merge.
+        let mut forged = rev.clone();
+        if let OwnedState::Revealed { assigned_state, .. } = &mut forged {
+            assigned_state.value = assigned_state.value.wrapping_add(1);
+        }
+
+        assert_eq!(
+            conf.into_revealed(forged).unwrap_err(),
+            Error::InvalidOpening
+        );
+    }
+
+    #[test]
+    fn test_verify_opening_rejects_forged_value() {
+        let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
+            .unwrap()
+            .into_discrete_state();
+
+        let rev = ass[1].clone();
+        let conf = rev.commit_conceal();
+
+        let revealed = rev.assigned_state().unwrap();
+        let confidential = conf.confidential_state().unwrap();
+
+        // The real value and blinding factor genuinely open the commitment.
+        assert!(PedersenStrategy::verify_opening(revealed, confidential));
+
+        // A forged value with the same blinding factor does not.
+        let mut forged = revealed.clone();
+        forged.value = forged.value.wrapping_add(1);
+        assert!(!PedersenStrategy::verify_opening(&forged, confidential));
+    }
+
+    #[test]
+    fn test_verify_balance() {
+        let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
+            .unwrap()
+            .into_discrete_state();
+
+        let rev = ass[1].clone();
+        let other = ass[0].clone();
+
+        // The same commitment on both sides of a transition always balances.
+        let same = Assignments::DiscreteFiniteField(vec![rev.clone()]);
+        assert!(Assignments::verify_balance(
+            &[same.clone()],
+            &[same.clone()]
+        ));
+
+        // Unrelated input and output amounts do not.
+        let different = Assignments::DiscreteFiniteField(vec![other]);
+        assert!(!Assignments::verify_balance(&[same], &[different]));
+    }
+
     #[test]
     fn test_into_revealed_assignements_ownedstates() {
         let assignment = Assignments::strict_decode(&HASH_VARIANT[..])
@@ -364,4 +828,207 @@ mod test {
 
         assert_eq!(merged, expected_rights);
     }
+
+    #[test]
+    fn test_non_unique_state_rejected() {
+        let assignment = Assignments::strict_decode(&HASH_VARIANT[..])
+            .unwrap()
+            .to_custom_state();
+        let rev = assignment[3].clone();
+
+        // Two assignments within one owned-right slot that both reveal to
+        // the *same* datum violate non-fungible uniqueness, even though each
+        // pair of states merges cleanly on its own.
+        let first = Assignments::CustomData(vec![rev.clone(), rev.clone()]);
+        let second = Assignments::CustomData(vec![rev.clone(), rev]);
+
+        assert_eq!(
+            first.into_revealed(second).unwrap_err(),
+            Error::NonUniqueState
+        );
+    }
+
+    #[test]
+    fn test_is_fungible() {
+        assert!(Assignments::DiscreteFiniteField(vec![]).is_fungible());
+        assert!(!Assignments::CustomData(vec![]).is_fungible());
+        assert!(!Assignments::Declarative(vec![]).is_fungible());
+    }
+
+    #[test]
+    fn test_declarative_duplicate_datum_allowed() {
+        let assignment = Assignments::strict_decode(&HASH_VARIANT[..])
+            .unwrap()
+            .to_custom_state();
+        let seal_definition = match &assignment[0] {
+            OwnedState::Revealed { seal_definition, .. } => {
+                seal_definition.clone()
+            }
+            _ => unreachable!("index 0 is the Revealed variant"),
+        };
+
+        // Declarative rights have no revealed datum to distinguish — every
+        // revealed assignment in a slot is the same unit value — so a slot
+        // assigning a declarative right to more than one seal, an ordinary
+        // and valid pattern, must not trip the non-fungible uniqueness check
+        // the way a duplicated `CustomData` datum would
+        // (`test_non_unique_state_rejected` above).
+        let declarative = || OwnedState::<DeclarativeStrategy>::Revealed {
+            seal_definition: seal_definition.clone(),
+            assigned_state: Default::default(),
+        };
+        let first =
+            Assignments::Declarative(vec![declarative(), declarative()]);
+        let second =
+            Assignments::Declarative(vec![declarative(), declarative()]);
+
+        assert!(first.into_revealed(second).is_ok());
+    }
+
+    /// Asserts the CRDT join laws (idempotence, commutativity, associativity)
+    /// hold for every pair (and triple) drawn from `lattice`. Shared by the
+    /// `OwnedState`, `Assignments` and `OwnedRightsInner` law tests below so
+    /// the three only differ in how they build their four-element lattice.
+    fn assert_semilattice_laws<T>(lattice: &[T])
+    where
+        T: IntoRevealed + Clone + PartialEq + std::fmt::Debug,
+    {
+        for a in lattice {
+            // idempotence: joining a value with itself is a no-op
+            assert_eq!(a.clone().into_revealed(a.clone()).unwrap(), a.clone());
+
+            for b in lattice {
+                // commutativity
+                assert_eq!(
+                    a.clone().into_revealed(b.clone()).unwrap(),
+                    b.clone().into_revealed(a.clone()).unwrap()
+                );
+
+                for c in lattice {
+                    // associativity
+                    let left = a
+                        .clone()
+                        .into_revealed(b.clone())
+                        .unwrap()
+                        .into_revealed(c.clone())
+                        .unwrap();
+                    let right = a
+                        .clone()
+                        .into_revealed(
+                            b.clone().into_revealed(c.clone()).unwrap(),
+                        )
+                        .unwrap();
+                    assert_eq!(left, right);
+                }
+            }
+        }
+    }
+
+    // The reveal order forms a bounded join-semilattice, so `into_revealed`
+    // must be commutative, associative and idempotent (the CRDT join laws).
+    #[test]
+    fn test_reveal_merge_semilattice_laws() {
+        let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
+            .unwrap()
+            .into_discrete_state();
+
+        let rev = ass[1].clone();
+        let conf = rev.commit_conceal();
+        let mut conf_state = rev.clone();
+        conf_state.conceal_state();
+        let seal = rev.seal_definition_confidential();
+        let conf_seal = OwnedState::<PedersenStrategy>::ConfidentialSeal {
+            seal_definition: seal,
+            assigned_state: rev.assigned_state().unwrap().clone(),
+        };
+
+        assert_semilattice_laws(&[rev, conf, conf_state, conf_seal]);
+    }
+
+    // Same CRDT join laws, but for `Assignments`, which merges a whole
+    // vector of owned states per owned-right slot rather than a single one.
+    #[test]
+    fn test_assignments_semilattice_laws() {
+        let assignment = Assignments::strict_decode(&HASH_VARIANT[..])
+            .unwrap()
+            .to_custom_state();
+
+        let rev = assignment[3].clone();
+        let conf = rev.commit_conceal();
+        let mut conf_state = rev.clone();
+        conf_state.conceal_state();
+        let seal = rev.seal_definition_confidential();
+        let conf_seal = OwnedState::<HashStrategy>::ConfidentialSeal {
+            seal_definition: seal,
+            assigned_state: rev.assigned_state().unwrap().clone(),
+        };
+
+        let lattice = [rev, conf, conf_state, conf_seal]
+            .map(|state| Assignments::CustomData(vec![state]));
+
+        assert_semilattice_laws(&lattice);
+    }
+
+    // Same CRDT join laws again, one level up: `OwnedRightsInner` merges a
+    // map of owned-right slots, each an `Assignments` group.
+    #[test]
+    fn test_owned_rights_inner_semilattice_laws() {
+        let assignment = Assignments::strict_decode(&HASH_VARIANT[..])
+            .unwrap()
+            .to_custom_state();
+
+        let rev = assignment[3].clone();
+        let conf = rev.commit_conceal();
+        let mut conf_state = rev.clone();
+        conf_state.conceal_state();
+        let seal = rev.seal_definition_confidential();
+        let conf_seal = OwnedState::<HashStrategy>::ConfidentialSeal {
+            seal_definition: seal,
+            assigned_state: rev.assigned_state().unwrap().clone(),
+        };
+
+        let lattice = [rev, conf, conf_state, conf_seal].map(|state| {
+            let assignment = Assignments::CustomData(vec![state]);
+            let owned_rights: OwnedRightsInner =
+                bmap! { 1usize => assignment }.into();
+            owned_rights
+        });
+
+        assert_semilattice_laws(&lattice);
+    }
+
+    #[test]
+    fn test_merge_reveal_all_batch() {
+        let ass = Assignments::strict_decode(&PEDERSAN_VARIANT[..])
+            .unwrap()
+            .into_discrete_state();
+
+        let rev = ass[1].clone();
+        let conf = rev.commit_conceal();
+        let mut conf_state = rev.clone();
+        conf_state.conceal_state();
+
+        // Folding every exposure of the same state yields the fully
+        // revealed one, regardless of order.
+        let merged = IntoRevealed::merge_reveal_all(vec![
+            conf.clone(),
+            conf_state.clone(),
+            rev.clone(),
+        ])
+        .unwrap();
+        assert_eq!(merged, Some(rev.clone()));
+
+        // Empty input folds to nothing.
+        let empty: Vec<OwnedState<PedersenStrategy>> = vec![];
+        assert_eq!(IntoRevealed::merge_reveal_all(empty).unwrap(), None);
+
+        // The lenient variant keeps the maximally-revealed value and reports
+        // conflicts instead of aborting.
+        let other = ass[0].clone();
+        let report = IntoRevealed::merge_reveal_all_lenient(vec![
+            conf, other, conf_state, rev.clone(),
+        ]);
+        assert_eq!(report.merged, Some(rev));
+        assert_eq!(report.conflicts, vec![Error::OwnedStateMismatch]);
+    }
 }